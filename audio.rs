@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+/// Move/bump/win clips, loaded once at `Startup` so playing a sound is just
+/// spawning an `AudioBundle` with a cloned handle rather than re-hitting disk.
+#[derive(Resource)]
+pub struct AudioAssets {
+    pub move_sound: Handle<AudioSource>,
+    pub bump_sound: Handle<AudioSource>,
+    pub win_sound: Handle<AudioSource>,
+}
+
+pub fn load_audio_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(AudioAssets {
+        move_sound: asset_server.load("sounds/move.ogg"),
+        bump_sound: asset_server.load("sounds/bump.ogg"),
+        win_sound: asset_server.load("sounds/win.ogg"),
+    });
+}
+
+pub fn play_move_sound(commands: &mut Commands, assets: &AudioAssets) {
+    commands.spawn(AudioBundle {
+        source: assets.move_sound.clone(),
+        settings: PlaybackSettings::DESPAWN,
+    });
+}
+
+pub fn play_bump_sound(commands: &mut Commands, assets: &AudioAssets) {
+    commands.spawn(AudioBundle {
+        source: assets.bump_sound.clone(),
+        settings: PlaybackSettings::DESPAWN,
+    });
+}
+
+pub fn play_win_sound(commands: &mut Commands, assets: &AudioAssets) {
+    commands.spawn(AudioBundle {
+        source: assets.win_sound.clone(),
+        settings: PlaybackSettings::DESPAWN,
+    });
+}
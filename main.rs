@@ -1,11 +1,19 @@
 use bevy::prelude::*;
-use rand::{thread_rng};
+use bevy::window::PrimaryWindow;
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
 use rand::prelude::SliceRandom;
-use std::time::Instant;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+const MAZE_EXPORT_PATH: &str = "maze_export.txt";
+
+mod audio;
+use audio::{load_audio_assets, play_bump_sound, play_move_sound, play_win_sound, AudioAssets};
 
 const TILE_SIZE: f32 = 32.0;
-const MAZE_WIDTH: usize = 21;
-const MAZE_HEIGHT: usize = 21;
+const BASE_MAZE_SIZE: usize = 21;
+const MAZE_GROWTH_PER_LEVEL: usize = 4;
 
 #[derive(Clone, Copy, PartialEq)]
 enum TileType {
@@ -13,6 +21,72 @@ enum TileType {
     Path,
 }
 
+#[derive(Resource, Clone, Copy, PartialEq, Debug)]
+enum MazeAlgorithm {
+    RecursiveBacktracker,
+    Prim,
+    RecursiveDivision,
+}
+
+impl MazeAlgorithm {
+    /// Derived from the maze seed (not `thread_rng`) so a given `--seed`
+    /// picks the same algorithm every time, not just the same carving.
+    fn from_seed(seed: MazeSeed) -> Self {
+        const VARIANTS: [MazeAlgorithm; 3] = [
+            MazeAlgorithm::RecursiveBacktracker,
+            MazeAlgorithm::Prim,
+            MazeAlgorithm::RecursiveDivision,
+        ];
+        let mut rng = StdRng::seed_from_u64(seed.0);
+        *VARIANTS.choose(&mut rng).unwrap()
+    }
+}
+
+/// Playing: the player is navigating the current level.
+/// Won: the goal was just reached and the level-clear screen is up.
+/// NextLevel: a transient state while the next maze is grown and spawned.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+enum GameState {
+    Playing,
+    Won,
+    NextLevel,
+}
+
+#[derive(Resource)]
+struct Level(u32);
+
+#[derive(Resource, Clone, Copy)]
+struct MazeSize {
+    width: usize,
+    height: usize,
+}
+
+impl MazeSize {
+    /// Mazes grow by `MAZE_GROWTH_PER_LEVEL` per level; since that's even and
+    /// `BASE_MAZE_SIZE` is odd, the side length stays odd at every level.
+    fn for_level(level: u32) -> Self {
+        let side = BASE_MAZE_SIZE + level as usize * MAZE_GROWTH_PER_LEVEL;
+        MazeSize { width: side, height: side }
+    }
+}
+
+/// Completed-level durations, oldest first, used to show per-level splits
+/// and a running total on the level-clear screen.
+#[derive(Resource, Default)]
+struct LevelTimes(Vec<Duration>);
+
+/// Seed behind the current maze's algorithm choice and carving RNG.
+/// Advanced deterministically on each level so a given `--seed` reproduces
+/// the same whole run.
+#[derive(Resource, Clone, Copy)]
+struct MazeSeed(u64);
+
+impl MazeSeed {
+    fn random() -> Self {
+        MazeSeed(thread_rng().gen())
+    }
+}
+
 #[derive(Resource)]
 struct Maze(Vec<Vec<TileType>>);
 
@@ -28,6 +102,10 @@ struct MoveTimer(Timer);
 #[derive(Resource)]
 struct StartTime(Instant);
 
+/// Whether the BFS shortest-path overlay is currently toggled on.
+#[derive(Resource, Default)]
+struct HintMode(bool);
+
 #[derive(Component)]
 struct Player;
 
@@ -41,73 +119,383 @@ struct WinText;
 struct MazeTile;
 
 #[derive(Component)]
-struct RestartButton;
+struct NextLevelButton;
+
+#[derive(Component)]
+struct HintTile;
 
 fn main() {
+    let level = 0;
+    let seed = parse_seed_arg().map(MazeSeed).unwrap_or_else(MazeSeed::random);
+    let algorithm = MazeAlgorithm::from_seed(seed);
+
+    let loaded = parse_load_arg()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|text| maze_from_ascii(&text));
+
+    let (maze, maze_size, player_start, goal_start) = match loaded {
+        Some(parsed) => (parsed.maze, parsed.size, parsed.player, parsed.goal),
+        None => {
+            let size = MazeSize::for_level(level);
+            let maze = generate_maze(algorithm, size, seed);
+            let goal = find_goal(&maze.0, size);
+            (maze, size, (1, 1), goal)
+        }
+    };
+
     App::new()
         .insert_resource(ClearColor(Color::BLACK))
-        .insert_resource(PlayerPosition(1, 1))
-        .insert_resource(generate_maze())
+        .insert_resource(PlayerPosition(player_start.0, player_start.1))
+        .insert_resource(GoalPosition(goal_start.0, goal_start.1))
+        .insert_resource(algorithm)
+        .insert_resource(seed)
+        .insert_resource(maze_size)
+        .insert_resource(maze)
         .insert_resource(MoveTimer(Timer::from_seconds(0.12, TimerMode::Repeating)))
         .insert_resource(StartTime(Instant::now()))
+        .insert_resource(Level(level))
+        .insert_resource(GameState::Playing)
+        .insert_resource(LevelTimes::default())
+        .insert_resource(HintMode::default())
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
                 title: "Rust Maze Escape".into(),
-                resolution: (MAZE_WIDTH as f32 * TILE_SIZE, MAZE_HEIGHT as f32 * TILE_SIZE).into(),
+                resolution: (maze_size.width as f32 * TILE_SIZE, maze_size.height as f32 * TILE_SIZE).into(),
                 ..default()
             }),
             ..default()
         }))
         .add_systems(Startup, setup)
+        .add_systems(Startup, load_audio_assets)
         .add_systems(Update, player_input)
-        .add_systems(Update, restart_button_system)
+        .add_systems(Update, level_transition_system)
+        .add_systems(Update, hint_system)
+        .add_systems(Update, maze_export_system)
         .run();
 }
 
-fn generate_maze() -> Maze {
-    let mut maze = vec![vec![TileType::Wall; MAZE_WIDTH]; MAZE_HEIGHT];
+fn generate_maze(algorithm: MazeAlgorithm, size: MazeSize, seed: MazeSeed) -> Maze {
+    let mut rng = StdRng::seed_from_u64(seed.0);
+    let grid = match algorithm {
+        MazeAlgorithm::RecursiveBacktracker => generate_maze_recursive_backtracker(size, &mut rng),
+        MazeAlgorithm::Prim => generate_maze_prim(size, &mut rng),
+        MazeAlgorithm::RecursiveDivision => generate_maze_recursive_division(size, &mut rng),
+    };
+    Maze(grid)
+}
 
-    fn carve(x: usize, y: usize, maze: &mut Vec<Vec<TileType>>) {
-        let mut rng = thread_rng();
-        let mut dirs = vec![(2, 0), (-2, 0), (0, 2), (0, -2)];
-        dirs.shuffle(&mut rng);
+/// Iterative equivalent of the classic recursive backtracker: a `Vec` stands
+/// in for the call stack, so maze size is no longer bounded by stack depth.
+/// Each iteration peeks the top cell, carves toward a random uncarved
+/// neighbor two cells away and pushes it, or pops when none remain.
+fn generate_maze_recursive_backtracker(size: MazeSize, rng: &mut impl Rng) -> Vec<Vec<TileType>> {
+    let mut maze = vec![vec![TileType::Wall; size.width]; size.height];
+    maze[1][1] = TileType::Path;
 
-        for (dx, dy) in dirs {
+    let mut stack: Vec<(usize, usize)> = vec![(1, 1)];
+
+    while let Some(&(x, y)) = stack.last() {
+        let mut candidates = Vec::new();
+        for (dx, dy) in [(2, 0), (-2, 0), (0, 2), (0, -2)] {
             let nx = x as isize + dx;
             let ny = y as isize + dy;
 
-            if nx > 0 && ny > 0 && (nx as usize) < MAZE_WIDTH - 1 && (ny as usize) < MAZE_HEIGHT - 1 {
+            if nx > 0 && ny > 0 && (nx as usize) < size.width - 1 && (ny as usize) < size.height - 1 {
                 if maze[ny as usize][nx as usize] == TileType::Wall {
-                    maze[ny as usize][nx as usize] = TileType::Path;
-                    maze[(y as isize + dy / 2) as usize][(x as isize + dx / 2) as usize] = TileType::Path;
-                    carve(nx as usize, ny as usize, maze);
+                    candidates.push((nx as usize, ny as usize, dx, dy));
                 }
             }
         }
+
+        if let Some(&(nx, ny, dx, dy)) = candidates.choose(rng) {
+            maze[ny][nx] = TileType::Path;
+            maze[(y as isize + dy / 2) as usize][(x as isize + dx / 2) as usize] = TileType::Path;
+            stack.push((nx, ny));
+        } else {
+            stack.pop();
+        }
     }
 
+    maze
+}
+
+/// Prim's algorithm: grow a single tree from (1,1) by repeatedly carving a
+/// random frontier cell into its already-carved neighborhood. Produces many
+/// short dead-ends compared to the backtracker's long winding corridors.
+fn generate_maze_prim(size: MazeSize, rng: &mut impl Rng) -> Vec<Vec<TileType>> {
+    let mut maze = vec![vec![TileType::Wall; size.width]; size.height];
+
+    fn wall_neighbors(x: usize, y: usize, size: MazeSize, maze: &[Vec<TileType>]) -> Vec<(usize, usize)> {
+        let mut neighbors = Vec::new();
+        for (dx, dy) in [(2, 0), (-2, 0), (0, 2), (0, -2)] {
+            let nx = x as isize + dx;
+            let ny = y as isize + dy;
+            if nx > 0 && ny > 0 && (nx as usize) < size.width - 1 && (ny as usize) < size.height - 1 {
+                if maze[ny as usize][nx as usize] == TileType::Wall {
+                    neighbors.push((nx as usize, ny as usize));
+                }
+            }
+        }
+        neighbors
+    }
+
+    fn path_neighbors(x: usize, y: usize, size: MazeSize, maze: &[Vec<TileType>]) -> Vec<(usize, usize)> {
+        let mut neighbors = Vec::new();
+        for (dx, dy) in [(2, 0), (-2, 0), (0, 2), (0, -2)] {
+            let nx = x as isize + dx;
+            let ny = y as isize + dy;
+            if nx > 0 && ny > 0 && (nx as usize) < size.width - 1 && (ny as usize) < size.height - 1 {
+                if maze[ny as usize][nx as usize] == TileType::Path {
+                    neighbors.push((nx as usize, ny as usize));
+                }
+            }
+        }
+        neighbors
+    }
+
+    maze[1][1] = TileType::Path;
+    let mut frontier = wall_neighbors(1, 1, size, &maze);
+
+    while !frontier.is_empty() {
+        let idx = rng.gen_range(0..frontier.len());
+        let (fx, fy) = frontier.swap_remove(idx);
+
+        if maze[fy][fx] == TileType::Path {
+            continue;
+        }
+
+        let carved_neighbors = path_neighbors(fx, fy, size, &maze);
+        let Some(&(nx, ny)) = carved_neighbors.choose(rng) else {
+            continue;
+        };
+
+        maze[fy][fx] = TileType::Path;
+        maze[(fy + ny) / 2][(fx + nx) / 2] = TileType::Path;
+        frontier.extend(wall_neighbors(fx, fy, size, &maze));
+    }
+
+    maze
+}
+
+/// Recursive division: start from an open chamber and repeatedly bisect it
+/// with a wall on an even coordinate, leaving a single passage on an odd
+/// coordinate, until the sub-chambers are too small to split further.
+fn generate_maze_recursive_division(size: MazeSize, rng: &mut impl Rng) -> Vec<Vec<TileType>> {
+    let mut maze = vec![vec![TileType::Path; size.width]; size.height];
+
+    for x in 0..size.width {
+        maze[0][x] = TileType::Wall;
+        maze[size.height - 1][x] = TileType::Wall;
+    }
+    for y in 0..size.height {
+        maze[y][0] = TileType::Wall;
+        maze[y][size.width - 1] = TileType::Wall;
+    }
+
+    fn divide(maze: &mut Vec<Vec<TileType>>, x0: usize, y0: usize, x1: usize, y1: usize, rng: &mut impl Rng) {
+        let width = x1 - x0;
+        let height = y1 - y0;
+
+        if width == 0 && height == 0 {
+            return;
+        }
+
+        if width > height {
+            let even_xs: Vec<usize> = (x0 + 1..x1).step_by(2).collect();
+            if even_xs.is_empty() {
+                return;
+            }
+            let wall_x = *even_xs.choose(rng).unwrap();
+            for y in y0..=y1 {
+                maze[y][wall_x] = TileType::Wall;
+            }
+            let passage_y = *(y0..=y1).step_by(2).collect::<Vec<_>>().choose(rng).unwrap();
+            maze[passage_y][wall_x] = TileType::Path;
+
+            divide(maze, x0, y0, wall_x - 1, y1, rng);
+            divide(maze, wall_x + 1, y0, x1, y1, rng);
+        } else {
+            let even_ys: Vec<usize> = (y0 + 1..y1).step_by(2).collect();
+            if even_ys.is_empty() {
+                return;
+            }
+            let wall_y = *even_ys.choose(rng).unwrap();
+            for x in x0..=x1 {
+                maze[wall_y][x] = TileType::Wall;
+            }
+            let passage_x = *(x0..=x1).step_by(2).collect::<Vec<_>>().choose(rng).unwrap();
+            maze[wall_y][passage_x] = TileType::Path;
+
+            divide(maze, x0, y0, x1, wall_y - 1, rng);
+            divide(maze, x0, wall_y + 1, x1, y1, rng);
+        }
+    }
+
+    divide(&mut maze, 1, 1, size.width - 2, size.height - 2, rng);
     maze[1][1] = TileType::Path;
-    carve(1, 1, &mut maze);
-    Maze(maze)
+    maze
+}
+
+/// Breadth-first search over `Path` cells, returning the shortest route from
+/// `start` to `goal` inclusive, or `None` if the goal is unreachable.
+fn bfs_shortest_path(
+    maze: &[Vec<TileType>],
+    size: MazeSize,
+    start: (usize, usize),
+    goal: (usize, usize),
+) -> Option<Vec<(usize, usize)>> {
+    let mut visited = vec![vec![false; size.width]; size.height];
+    let mut came_from: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    visited[start.1][start.0] = true;
+    queue.push_back(start);
+
+    while let Some(current) = queue.pop_front() {
+        if current == goal {
+            let mut path = vec![current];
+            let mut node = current;
+            while let Some(&prev) = came_from.get(&node) {
+                path.push(prev);
+                node = prev;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let (x, y) = current;
+        let neighbors = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+
+        for (nx, ny) in neighbors {
+            if nx < size.width && ny < size.height && !visited[ny][nx] && maze[ny][nx] == TileType::Path {
+                visited[ny][nx] = true;
+                came_from.insert((nx, ny), current);
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    None
+}
+
+fn find_goal(maze: &[Vec<TileType>], size: MazeSize) -> (usize, usize) {
+    let mut goal_pos = (size.width - 2, size.height - 2);
+    'outer: for y in (1..size.height - 1).rev() {
+        for x in (1..size.width - 1).rev() {
+            if maze[y][x] == TileType::Path {
+                goal_pos = (x, y);
+                break 'outer;
+            }
+        }
+    }
+    goal_pos
+}
+
+/// Renders the grid as `#`/`.` lines with `O` marking the player and `X`
+/// marking the goal, so a run can be dumped to a file and shared or replayed.
+fn maze_to_ascii(maze: &Maze, player: &PlayerPosition, goal: &GoalPosition) -> String {
+    let mut lines = Vec::with_capacity(maze.0.len());
+    for (y, row) in maze.0.iter().enumerate() {
+        let mut line = String::with_capacity(row.len());
+        for (x, &tile) in row.iter().enumerate() {
+            let ch = if (x, y) == (player.0, player.1) {
+                'O'
+            } else if (x, y) == (goal.0, goal.1) {
+                'X'
+            } else {
+                match tile {
+                    TileType::Wall => '#',
+                    TileType::Path => '.',
+                }
+            };
+            line.push(ch);
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+struct ParsedMaze {
+    maze: Maze,
+    size: MazeSize,
+    player: (usize, usize),
+    goal: (usize, usize),
+}
+
+/// Inverse of `maze_to_ascii`: any character other than `.`, `O`, or `X` is
+/// treated as a wall, so the border doesn't need special-casing.
+fn maze_from_ascii(text: &str) -> Option<ParsedMaze> {
+    let lines: Vec<&str> = text.lines().filter(|line| !line.is_empty()).collect();
+    let height = lines.len();
+    let width = lines.iter().map(|line| line.chars().count()).max()?;
+
+    if height == 0 || width == 0 {
+        return None;
+    }
+
+    let mut grid = vec![vec![TileType::Wall; width]; height];
+    let mut player = (1, 1);
+    let mut goal = (width.saturating_sub(2), height.saturating_sub(2));
+
+    for (y, line) in lines.iter().enumerate() {
+        for (x, ch) in line.chars().enumerate() {
+            match ch {
+                '.' => grid[y][x] = TileType::Path,
+                'O' => {
+                    grid[y][x] = TileType::Path;
+                    player = (x, y);
+                }
+                'X' => {
+                    grid[y][x] = TileType::Path;
+                    goal = (x, y);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Some(ParsedMaze {
+        maze: Maze(grid),
+        size: MazeSize { width, height },
+        player,
+        goal,
+    })
+}
+
+fn cli_arg_value(flag: &str) -> Option<String> {
+    let mut args = std::env::args();
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        }
+    }
+    None
+}
+
+fn parse_seed_arg() -> Option<u64> {
+    cli_arg_value("--seed").and_then(|value| value.parse().ok())
+}
+
+fn parse_load_arg() -> Option<String> {
+    cli_arg_value("--load")
 }
 
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     maze: Res<Maze>,
+    size: Res<MazeSize>,
+    player: Res<PlayerPosition>,
+    goal: Res<GoalPosition>,
 ) {
     commands.spawn(Camera2dBundle::default());
 
-    let mut goal_pos = (MAZE_WIDTH - 2, MAZE_HEIGHT - 2);
-    'outer: for y in (1..MAZE_HEIGHT - 1).rev() {
-        for x in (1..MAZE_WIDTH - 1).rev() {
-            if maze.0[y][x] == TileType::Path {
-                goal_pos = (x, y);
-                break 'outer;
-            }
-        }
-    }
-    commands.insert_resource(GoalPosition(goal_pos.0, goal_pos.1));
+    let goal_pos = (goal.0, goal.1);
 
     for (y, row) in maze.0.iter().enumerate() {
         for (x, &tile) in row.iter().enumerate() {
@@ -124,8 +512,8 @@ fn setup(
                         ..default()
                     },
                     transform: Transform::from_translation(Vec3::new(
-                        x as f32 * TILE_SIZE - (MAZE_WIDTH as f32 / 2.0 * TILE_SIZE),
-                        y as f32 * TILE_SIZE - (MAZE_HEIGHT as f32 / 2.0 * TILE_SIZE),
+                        x as f32 * TILE_SIZE - (size.width as f32 / 2.0 * TILE_SIZE),
+                        y as f32 * TILE_SIZE - (size.height as f32 / 2.0 * TILE_SIZE),
                         0.0,
                     )),
                     ..default()
@@ -143,8 +531,8 @@ fn setup(
                 ..default()
             },
             transform: Transform::from_translation(Vec3::new(
-                goal_pos.0 as f32 * TILE_SIZE - (MAZE_WIDTH as f32 / 2.0 * TILE_SIZE),
-                goal_pos.1 as f32 * TILE_SIZE - (MAZE_HEIGHT as f32 / 2.0 * TILE_SIZE),
+                goal_pos.0 as f32 * TILE_SIZE - (size.width as f32 / 2.0 * TILE_SIZE),
+                goal_pos.1 as f32 * TILE_SIZE - (size.height as f32 / 2.0 * TILE_SIZE),
                 0.5,
             )),
             ..default()
@@ -160,8 +548,8 @@ fn setup(
                 ..default()
             },
             transform: Transform::from_translation(Vec3::new(
-                1.0 * TILE_SIZE - (MAZE_WIDTH as f32 / 2.0 * TILE_SIZE),
-                1.0 * TILE_SIZE - (MAZE_HEIGHT as f32 / 2.0 * TILE_SIZE),
+                player.0 as f32 * TILE_SIZE - (size.width as f32 / 2.0 * TILE_SIZE),
+                player.1 as f32 * TILE_SIZE - (size.height as f32 / 2.0 * TILE_SIZE),
                 1.0,
             )),
             ..default()
@@ -173,19 +561,26 @@ fn setup(
 fn player_input(
     mut commands: Commands,
     keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
     time: Res<Time>,
     mut move_timer: ResMut<MoveTimer>,
     mut player_query: Query<(Entity, &mut Transform), With<Player>>,
     mut pos: ResMut<PlayerPosition>,
     maze: Res<Maze>,
+    size: Res<MazeSize>,
     goal: Res<GoalPosition>,
-    win_text_query: Query<Entity, With<WinText>>,
+    level: Res<Level>,
+    mut level_times: ResMut<LevelTimes>,
+    mut game_state: ResMut<GameState>,
     asset_server: Res<AssetServer>,
     maze_entities: Query<Entity, With<MazeTile>>,
     goal_query: Query<Entity, With<Goal>>,
     start_time: Res<StartTime>,
+    audio_assets: Res<AudioAssets>,
 ) {
-    if !win_text_query.is_empty() {
+    if *game_state != GameState::Playing {
         return;
     }
 
@@ -193,6 +588,8 @@ fn player_input(
         return;
     }
 
+    const STICK_DEADZONE: f32 = 0.3;
+
     let mut dx = 0;
     let mut dy = 0;
 
@@ -204,6 +601,27 @@ fn player_input(
         dx -= 1;
     } else if keys.pressed(KeyCode::ArrowRight) {
         dx += 1;
+    } else if let Some(gamepad) = gamepads.iter().next() {
+        let stick_x = gamepad_axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+            .unwrap_or(0.0);
+        let stick_y = gamepad_axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+            .unwrap_or(0.0);
+
+        if gamepad_buttons.pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadUp)) {
+            dy += 1;
+        } else if gamepad_buttons.pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadDown)) {
+            dy -= 1;
+        } else if gamepad_buttons.pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadLeft)) {
+            dx -= 1;
+        } else if gamepad_buttons.pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadRight)) {
+            dx += 1;
+        } else if stick_y.abs() >= STICK_DEADZONE && stick_y.abs() >= stick_x.abs() {
+            dy += if stick_y > 0.0 { 1 } else { -1 };
+        } else if stick_x.abs() >= STICK_DEADZONE {
+            dx += if stick_x > 0.0 { 1 } else { -1 };
+        }
     }
 
     if dx == 0 && dy == 0 {
@@ -213,22 +631,24 @@ fn player_input(
     let new_x = (pos.0 as isize + dx) as usize;
     let new_y = (pos.1 as isize + dy) as usize;
 
-    if new_x < MAZE_WIDTH && new_y < MAZE_HEIGHT && maze.0[new_y][new_x] == TileType::Path {
+    if new_x < size.width && new_y < size.height && maze.0[new_y][new_x] == TileType::Path {
         pos.0 = new_x;
         pos.1 = new_y;
+        play_move_sound(&mut commands, &audio_assets);
 
         for (_, mut transform) in player_query.iter_mut() {
             transform.translation = Vec3::new(
-                new_x as f32 * TILE_SIZE - (MAZE_WIDTH as f32 / 2.0 * TILE_SIZE),
-                new_y as f32 * TILE_SIZE - (MAZE_HEIGHT as f32 / 2.0 * TILE_SIZE),
+                new_x as f32 * TILE_SIZE - (size.width as f32 / 2.0 * TILE_SIZE),
+                new_y as f32 * TILE_SIZE - (size.height as f32 / 2.0 * TILE_SIZE),
                 1.0,
             );
         }
 
-        if pos.0 == goal.0 && pos.1 == goal.1 && win_text_query.is_empty() {
-            let elapsed = start_time.0.elapsed();
-            let seconds = elapsed.as_secs();
-            let millis = elapsed.subsec_millis();
+        if pos.0 == goal.0 && pos.1 == goal.1 {
+            play_win_sound(&mut commands, &audio_assets);
+            let split = start_time.0.elapsed();
+            level_times.0.push(split);
+            let total: Duration = level_times.0.iter().sum();
 
             for (entity, _) in player_query.iter_mut() {
                 commands.entity(entity).despawn_recursive();
@@ -242,7 +662,14 @@ fn player_input(
 
             commands.spawn((
                 TextBundle::from_section(
-                    format!("You win!\nTime: {}.{:03} seconds", seconds, millis),
+                    format!(
+                        "Level {} cleared!\nSplit: {}.{:03}s\nTotal: {}.{:03}s",
+                        level.0 + 1,
+                        split.as_secs(),
+                        split.subsec_millis(),
+                        total.as_secs(),
+                        total.subsec_millis(),
+                    ),
                     TextStyle {
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: 48.0,
@@ -250,8 +677,8 @@ fn player_input(
                     },
                 ).with_style(Style {
                     position_type: PositionType::Absolute,
-                    top: Val::Percent(35.0),
-                    left: Val::Percent(30.0),
+                    top: Val::Percent(30.0),
+                    left: Val::Percent(25.0),
                     ..default()
                 }),
                 WinText,
@@ -260,12 +687,12 @@ fn player_input(
             commands.spawn((
                 ButtonBundle {
                     style: Style {
-                        width: Val::Px(150.0),
+                        width: Val::Px(180.0),
                         height: Val::Px(65.0),
                         margin: UiRect::all(Val::Auto),
                         position_type: PositionType::Absolute,
                         top: Val::Percent(60.0),
-                        left: Val::Percent(37.0),
+                        left: Val::Percent(35.0),
                         justify_content: JustifyContent::Center,
                         align_items: AlignItems::Center,
                         ..default()
@@ -273,11 +700,11 @@ fn player_input(
                     background_color: BackgroundColor(Color::DARK_GRAY),
                     ..default()
                 },
-                RestartButton,
+                NextLevelButton,
             ));
 
             commands.spawn(TextBundle::from_section(
-                "Restart",
+                "Next Level",
                 TextStyle {
                     font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                     font_size: 40.0,
@@ -286,33 +713,45 @@ fn player_input(
             ).with_style(Style {
                 position_type: PositionType::Absolute,
                 top: Val::Percent(63.0),
-                left: Val::Percent(40.0),
+                left: Val::Percent(37.0),
                 margin: UiRect::all(Val::Auto),
                 ..default()
             }));
+
+            *game_state = GameState::Won;
         }
+    } else {
+        play_bump_sound(&mut commands, &audio_assets);
     }
 }
 
-fn restart_button_system(
+fn level_transition_system(
     mut commands: Commands,
-    interaction_query: Query<(&Interaction, Entity), (Changed<Interaction>, With<RestartButton>)>,
+    interaction_query: Query<(&Interaction, Entity), (Changed<Interaction>, With<NextLevelButton>)>,
     mut maze_res: ResMut<Maze>,
+    mut size_res: ResMut<MazeSize>,
+    mut algorithm: ResMut<MazeAlgorithm>,
+    mut seed: ResMut<MazeSeed>,
+    mut level: ResMut<Level>,
     mut player_pos_res: ResMut<PlayerPosition>,
     mut goal_pos_res: ResMut<GoalPosition>,
+    mut game_state: ResMut<GameState>,
     win_text_query: Query<Entity, With<WinText>>,
     maze_entities: Query<Entity, With<MazeTile>>,
     goal_query: Query<Entity, With<Goal>>,
-    restart_button_query: Query<Entity, With<RestartButton>>,
+    next_level_button_query: Query<Entity, With<NextLevelButton>>,
     text_query: Query<Entity, With<Text>>,
     mut start_time: ResMut<StartTime>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
 ) {
     for (interaction, _entity) in &interaction_query {
         if *interaction == Interaction::Pressed {
+            *game_state = GameState::NextLevel;
+
             for entity in win_text_query.iter() {
                 commands.entity(entity).despawn_recursive();
             }
-            for entity in restart_button_query.iter() {
+            for entity in next_level_button_query.iter() {
                 commands.entity(entity).despawn_recursive();
             }
             for entity in text_query.iter() {
@@ -325,28 +764,28 @@ fn restart_button_system(
                 commands.entity(entity).despawn_recursive();
             }
 
-            let new_maze = generate_maze();
-            *maze_res = new_maze;
+            level.0 += 1;
+            let size = MazeSize::for_level(level.0);
+            *size_res = size;
+
+            *seed = MazeSeed(seed.0.wrapping_add(1));
+            *algorithm = MazeAlgorithm::from_seed(*seed);
+            *maze_res = generate_maze(*algorithm, size, *seed);
 
             player_pos_res.0 = 1;
             player_pos_res.1 = 1;
 
-            let maze = &maze_res.0;
-            let mut goal_pos = (MAZE_WIDTH - 2, MAZE_HEIGHT - 2);
-            'outer: for y in (1..MAZE_HEIGHT - 1).rev() {
-                for x in (1..MAZE_WIDTH - 1).rev() {
-                    if maze[y][x] == TileType::Path {
-                        goal_pos = (x, y);
-                        break 'outer;
-                    }
-                }
-            }
+            let goal_pos = find_goal(&maze_res.0, size);
             goal_pos_res.0 = goal_pos.0;
             goal_pos_res.1 = goal_pos.1;
 
-            for y in 0..MAZE_HEIGHT {
-                for x in 0..MAZE_WIDTH {
-                    let color = match maze[y][x] {
+            if let Ok(mut window) = window_query.get_single_mut() {
+                window.resolution = (size.width as f32 * TILE_SIZE, size.height as f32 * TILE_SIZE).into();
+            }
+
+            for (y, row) in maze_res.0.iter().enumerate() {
+                for (x, &tile) in row.iter().enumerate() {
+                    let color = match tile {
                         TileType::Wall => Color::DARK_GRAY,
                         TileType::Path => Color::WHITE,
                     };
@@ -358,8 +797,8 @@ fn restart_button_system(
                                 ..default()
                             },
                             transform: Transform::from_translation(Vec3::new(
-                                x as f32 * TILE_SIZE - (MAZE_WIDTH as f32 / 2.0 * TILE_SIZE),
-                                y as f32 * TILE_SIZE - (MAZE_HEIGHT as f32 / 2.0 * TILE_SIZE),
+                                x as f32 * TILE_SIZE - (size.width as f32 / 2.0 * TILE_SIZE),
+                                y as f32 * TILE_SIZE - (size.height as f32 / 2.0 * TILE_SIZE),
                                 0.0,
                             )),
                             ..default()
@@ -377,8 +816,8 @@ fn restart_button_system(
                         ..default()
                     },
                     transform: Transform::from_translation(Vec3::new(
-                        goal_pos.0 as f32 * TILE_SIZE - (MAZE_WIDTH as f32 / 2.0 * TILE_SIZE),
-                        goal_pos.1 as f32 * TILE_SIZE - (MAZE_HEIGHT as f32 / 2.0 * TILE_SIZE),
+                        goal_pos.0 as f32 * TILE_SIZE - (size.width as f32 / 2.0 * TILE_SIZE),
+                        goal_pos.1 as f32 * TILE_SIZE - (size.height as f32 / 2.0 * TILE_SIZE),
                         0.5,
                     )),
                     ..default()
@@ -394,8 +833,8 @@ fn restart_button_system(
                         ..default()
                     },
                     transform: Transform::from_translation(Vec3::new(
-                        1.0 * TILE_SIZE - (MAZE_WIDTH as f32 / 2.0 * TILE_SIZE),
-                        1.0 * TILE_SIZE - (MAZE_HEIGHT as f32 / 2.0 * TILE_SIZE),
+                        1.0 * TILE_SIZE - (size.width as f32 / 2.0 * TILE_SIZE),
+                        1.0 * TILE_SIZE - (size.height as f32 / 2.0 * TILE_SIZE),
                         1.0,
                     )),
                     ..default()
@@ -403,8 +842,82 @@ fn restart_button_system(
                 Player,
             ));
 
-            // 重置计时器
             start_time.0 = Instant::now();
+            *game_state = GameState::Playing;
         }
     }
 }
+
+/// Toggled with `H`: highlights the shortest route from the player to the
+/// goal. Recomputed whenever the player moves, cleared when toggled off.
+fn hint_system(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut hint_mode: ResMut<HintMode>,
+    maze: Res<Maze>,
+    size: Res<MazeSize>,
+    pos: Res<PlayerPosition>,
+    goal: Res<GoalPosition>,
+    game_state: Res<GameState>,
+    hint_tiles: Query<Entity, With<HintTile>>,
+) {
+    if keys.just_pressed(KeyCode::KeyH) {
+        hint_mode.0 = !hint_mode.0;
+    }
+
+    if !hint_mode.0 || *game_state != GameState::Playing {
+        for entity in hint_tiles.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    if !pos.is_changed() && !hint_tiles.is_empty() {
+        return;
+    }
+
+    for entity in hint_tiles.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let Some(path) = bfs_shortest_path(&maze.0, *size, (pos.0, pos.1), (goal.0, goal.1)) else {
+        return;
+    };
+
+    for (x, y) in path {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgba(0.2, 0.4, 1.0, 0.4),
+                    custom_size: Some(Vec2::splat(TILE_SIZE)),
+                    ..default()
+                },
+                transform: Transform::from_translation(Vec3::new(
+                    x as f32 * TILE_SIZE - (size.width as f32 / 2.0 * TILE_SIZE),
+                    y as f32 * TILE_SIZE - (size.height as f32 / 2.0 * TILE_SIZE),
+                    0.2,
+                )),
+                ..default()
+            },
+            HintTile,
+        ));
+    }
+}
+
+/// `F5` dumps the current maze to `MAZE_EXPORT_PATH` as ASCII; pass
+/// `--load <path>` to start from such a file instead of generating one.
+fn maze_export_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    maze: Res<Maze>,
+    pos: Res<PlayerPosition>,
+    goal: Res<GoalPosition>,
+) {
+    if !keys.just_pressed(KeyCode::F5) {
+        return;
+    }
+
+    let ascii = maze_to_ascii(&maze, &pos, &goal);
+    if let Err(err) = std::fs::write(MAZE_EXPORT_PATH, ascii) {
+        eprintln!("failed to export maze to {MAZE_EXPORT_PATH}: {err}");
+    }
+}